@@ -1,10 +1,11 @@
 extern crate sched;
 
-use sched::*;
+use sched::{Job, Scheduler};
+use sched::TimeSpan::Seconds;
 
 fn main() {
     let job = Job::every(5, Seconds).do_(Box::new(|| println!("Hello World"))).unwrap();
-    let mut sched = Scheduler::new();
+    let mut sched: Scheduler = Scheduler::new();
     sched.add_job(job);
 
     sched.run_forever();
@@ -2,9 +2,38 @@
 //! a task which is to be run at some time in the future.
 
 
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::fmt::{Formatter, Debug, Error};
-use chrono::{Duration, Local, DateTime};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use chrono::{Datelike, Duration, Local, DateTime, TimeZone, Timelike, Weekday};
+use rand::{self, Rng};
+use regex::Regex;
 
+use time::{ChronoTimeProvider, TimeProvider};
+
+/// A stable identifier for a `Job`, handed back by `Scheduler::add_job` so a
+/// specific job instance can be cancelled later even if its tags collide
+/// with other jobs.
+pub type JobId = usize;
+
+static NEXT_JOB_ID: AtomicUsize = AtomicUsize::new(0);
+
+fn next_job_id() -> JobId {
+    NEXT_JOB_ID.fetch_add(1, AtomicOrdering::SeqCst)
+}
+
+lazy_static! {
+    /// Matches the `"HH:MM:SS"` (or `"HH:MM"`) form used for day-granularity
+    /// and weekday jobs.
+    static ref DAILY_AT_RE: Regex = Regex::new(r"^([0-2]\d:)?[0-5]\d:[0-5]\d$").unwrap();
+    /// Matches the `"MM:SS"` form used for hour-granularity jobs.
+    static ref HOURLY_AT_RE: Regex = Regex::new(r"^([0-5]\d)?:[0-5]\d$").unwrap();
+    /// Matches the `":SS"` form used for minute-granularity jobs.
+    static ref MINUTE_AT_RE: Regex = Regex::new(r"^:[0-5]\d$").unwrap();
+}
 
 /// An alias for a boxed closure.
 pub type Func = Box<Fn() + Send + Sync>;
@@ -26,19 +55,90 @@ pub enum TimeSpan {
     Weeks,
 }
 
+/// The granularity that `.at()` was validated/anchored against, derived
+/// from a job's period unit (and weekday, which is always day-granularity).
+/// Shared between `parse_at` and `recompute_next_run` so the two can't
+/// silently disagree about what counts as "hourly".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Granularity {
+    Daily,
+    Hourly,
+    Minutely,
+    /// `.at()` isn't valid for this job's unit at all.
+    None,
+}
+
+/// Convert a count and a unit into a `chrono::Duration`.
+fn to_duration(n: i64, delta_type: TimeSpan) -> Duration {
+    match delta_type {
+        TimeSpan::Millisecond | TimeSpan::Milliseconds => Duration::milliseconds(n),
+        TimeSpan::Second | TimeSpan::Seconds => Duration::seconds(n),
+        TimeSpan::Minute | TimeSpan::Minutes => Duration::minutes(n),
+        TimeSpan::Hour | TimeSpan::Hours => Duration::hours(n),
+        TimeSpan::Day | TimeSpan::Days => Duration::days(n),
+        TimeSpan::Week | TimeSpan::Weeks => Duration::weeks(n),
+    }
+}
+
 
 /// A task that is designed to be run at some point in the future.
-pub struct Job {
-    duration: Duration,
+///
+/// `Job` is generic over its `TimeProvider` so tests can drive it with a
+/// `MockTimeProvider` instead of the real clock; in normal use the default
+/// `ChronoTimeProvider` (backed by `Local::now()`) is all you need.
+pub struct Job<Tp: TimeProvider = ChronoTimeProvider> {
+    period_lower: Duration,
+    period_upper: Option<Duration>,
     last_run: DateTime<Local>,
     next_run: Option<DateTime<Local>>,
     once_off: bool,
     name: Option<String>,
-    func: Option<Func>,
+    func: Option<Arc<dyn Fn() + Send + Sync>>,
     times_run: u32,
+    /// The unit last passed to `increment()`, used to decide which `.at()`
+    /// pattern applies and whether a weekday is required.
+    last_unit: Option<TimeSpan>,
+    /// A specific time-of-day set with `.at()`.
+    at_time: Option<(u32, u32, u32)>,
+    /// A specific day of the week set with `every_weekday()`.
+    weekday: Option<Weekday>,
+    /// A stable id, assigned once at construction, that survives renaming
+    /// and tag changes.
+    id: JobId,
+    tags: HashSet<String>,
+    _clock: PhantomData<Tp>,
+}
+
+/// Jobs order by their `next_run`, with the earliest deadline sorting as
+/// `Greater` so a `std::collections::BinaryHeap<Job<Tp>>` (a max-heap) pops
+/// the soonest-due job first. A job with no `next_run` never comes due, so
+/// it sorts below every job that does.
+impl<Tp: TimeProvider> Ord for Job<Tp> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.next_run, other.next_run) {
+            (Some(ours), Some(theirs)) => theirs.cmp(&ours),
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => Ordering::Equal,
+        }
+    }
 }
 
-impl Debug for Job {
+impl<Tp: TimeProvider> PartialOrd for Job<Tp> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Tp: TimeProvider> PartialEq for Job<Tp> {
+    fn eq(&self, other: &Self) -> bool {
+        self.next_run == other.next_run
+    }
+}
+
+impl<Tp: TimeProvider> Eq for Job<Tp> {}
+
+impl<Tp: TimeProvider> Debug for Job<Tp> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         match self.name {
             Some(ref n) => write!(f, "Job(name='{}')", n),
@@ -47,87 +147,259 @@ impl Debug for Job {
     }
 }
 
-impl Default for Job {
-    fn default() -> Job {
+impl<Tp: TimeProvider> Default for Job<Tp> {
+    fn default() -> Job<Tp> {
         Job {
-            last_run: Local::now(),
+            last_run: Tp::now(),
             next_run: None,
-            duration: Duration::zero(),
+            period_lower: Duration::zero(),
+            period_upper: None,
             once_off: false,
             name: None,
             func: None,
             times_run: 0,
+            last_unit: None,
+            at_time: None,
+            weekday: None,
+            id: next_job_id(),
+            tags: HashSet::new(),
+            _clock: PhantomData,
         }
     }
 }
 
-impl Job {
+impl<Tp: TimeProvider> Job<Tp> {
     /// Construct a bare Job.
+    ///
+    /// Rust won't fall back to the `ChronoTimeProvider` default to resolve
+    /// inference on its own, so a call site with nothing else pinning `Tp`
+    /// (a fresh `let`, a doctest) needs an explicit type, e.g. `let job:
+    /// Job = Job::new();` or `Job::<MockTimeProvider>::new()`.
     pub fn new() -> Self {
         Job::default()
     }
 
-    /// Give the job a name.
-    pub fn name(mut self, s: &str) -> Job {
-        self.name = Some(s.to_string());
-        self
-    }
-
     /// Construct a periodic job.
-    pub fn every(n: i64, delta_type: TimeSpan) -> Job {
+    pub fn every(n: i64, delta_type: TimeSpan) -> Self {
         let mut d = Job::new();
         d.increment(n, delta_type);
         d
     }
 
     /// Create a once off job.
-    pub fn in_(n: i64, delta_type: TimeSpan) -> Job {
+    pub fn in_(n: i64, delta_type: TimeSpan) -> Self {
         let mut d = Job::new();
         d.increment(n, delta_type);
         d.once_off = true;
         d
     }
 
+    /// Construct a job that runs once a week on the given weekday, at the
+    /// time of day set with `.at()`, e.g.
+    /// `Job::every_weekday(Weekday::Mon).at("06:00")`. Without `.at()` the
+    /// weekday is not applied: the job runs once a week, counting from when
+    /// it was created or last run.
+    pub fn every_weekday(weekday: Weekday) -> Self {
+        let mut d = Job::new();
+        d.increment(1, TimeSpan::Week);
+        d.weekday = Some(weekday);
+        d
+    }
+
+    /// Give the job a name.
+    pub fn name(mut self, s: &str) -> Self {
+        self.name = Some(s.to_string());
+        self
+    }
+
+    /// Tag the job so it can later be found or cancelled in bulk with
+    /// `Scheduler::get_jobs`/`Scheduler::clear`. Can be called more than
+    /// once to give a job several tags.
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tags.insert(tag.to_string());
+        self
+    }
+
+    /// The stable id assigned to this job when it was created.
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+
+    /// Check whether this job carries the given tag.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+
+    /// Check whether this job was given the provided name.
+    pub(crate) fn matches_name(&self, name: &str) -> bool {
+        self.name.as_ref().map(|n| n == name).unwrap_or(false)
+    }
+
     /// Add to the duration between runs.
     ///
-    ///     use sched::*;
-    ///     let job = Job::every(5, Minutes).and(10, Seconds);
-    pub fn and(mut self, n: i64, delta_type: TimeSpan) -> Job {
+    ///     use sched::Job;
+    ///     use sched::TimeSpan::{Minutes, Seconds};
+    ///     let job: Job = Job::every(5, Minutes).and(10, Seconds);
+    pub fn and(mut self, n: i64, delta_type: TimeSpan) -> Self {
         self.increment(n, delta_type);
         self
     }
 
     /// Increase the duration between runs by a certain amount.
     fn increment(&mut self, n: i64, delta_type: TimeSpan) {
-        let new_duration = match delta_type {
-            TimeSpan::Millisecond | TimeSpan::Milliseconds => Duration::milliseconds(n),
-            TimeSpan::Second | TimeSpan::Seconds => Duration::seconds(n),
-            TimeSpan::Minute | TimeSpan::Minutes => Duration::minutes(n),
-            TimeSpan::Hour | TimeSpan::Hours => Duration::hours(n),
-            TimeSpan::Day | TimeSpan::Days => Duration::days(n),
-            TimeSpan::Week | TimeSpan::Weeks => Duration::weeks(n),
-        };
+        self.period_lower += to_duration(n, delta_type);
+        self.last_unit = Some(delta_type);
 
+        // Update the next_run
+        self.recompute_next_run();
+    }
 
-        self.duration = self.duration + new_duration;
+    /// Turn this job's fixed period into a random range, e.g.
+    /// `Job::every(30, Minutes).to(45, Minutes)` waits somewhere between 30
+    /// and 45 minutes between runs. This is handy for staggering many jobs
+    /// that would otherwise all fire on the same boundary.
+    pub fn to(mut self, n: i64, delta_type: TimeSpan) -> Self {
+        self.period_upper = Some(to_duration(n, delta_type));
+        self.recompute_next_run();
+        self
+    }
 
-        // Update the next_run
-        self.next_run = Some(self.last_run + self.duration);
+    /// Anchor the job to a specific time of day, given as `"HH:MM:SS"` or
+    /// `"HH:MM"` for day and weekday jobs, `"MM:SS"` or `":SS"` for hour
+    /// jobs, and `":SS"` for minute jobs.
+    ///
+    ///     use sched::{Job, Weekday};
+    ///     use sched::TimeSpan::Days;
+    ///     let job: Job = Job::every(1, Days).at("10:30:00").unwrap();
+    ///     let job: Job = Job::every_weekday(Weekday::Mon).at("06:00").unwrap();
+    pub fn at(mut self, time: &str) -> Result<Self, String> {
+        let (hour, minute, second) = self.parse_at(time)?;
+        self.at_time = Some((hour, minute, second));
+        self.recompute_next_run();
+        Ok(self)
+    }
+
+    /// The granularity `.at()` should be validated/anchored against, based
+    /// on this job's period unit.
+    fn granularity(&self) -> Granularity {
+        if self.weekday.is_some() {
+            return Granularity::Daily;
+        }
+        match self.last_unit {
+            Some(TimeSpan::Day) | Some(TimeSpan::Days) |
+            Some(TimeSpan::Week) | Some(TimeSpan::Weeks) => Granularity::Daily,
+            Some(TimeSpan::Hour) | Some(TimeSpan::Hours) => Granularity::Hourly,
+            Some(TimeSpan::Minute) | Some(TimeSpan::Minutes) => Granularity::Minutely,
+            _ => Granularity::None,
+        }
+    }
+
+    /// Validate `time` against the regex appropriate for this job's
+    /// granularity and split it out into hour/minute/second components.
+    fn parse_at(&self, time: &str) -> Result<(u32, u32, u32), String> {
+        match self.granularity() {
+            Granularity::Daily => {
+                if !DAILY_AT_RE.is_match(time) {
+                    return Err(format!("'{}' doesn't match the expected HH:MM:SS form", time));
+                }
+                let parts: Vec<&str> = time.split(':').collect();
+                if parts.len() == 3 {
+                    Ok((parts[0].parse().unwrap(), parts[1].parse().unwrap(), parts[2].parse().unwrap()))
+                } else {
+                    Ok((parts[0].parse().unwrap(), parts[1].parse().unwrap(), 0))
+                }
+            }
+            Granularity::Hourly => {
+                if !HOURLY_AT_RE.is_match(time) {
+                    return Err(format!("'{}' doesn't match the expected MM:SS form", time));
+                }
+                let parts: Vec<&str> = time.split(':').collect();
+                let minute = if parts[0].is_empty() { 0 } else { parts[0].parse().unwrap() };
+                Ok((0, minute, parts[1].parse().unwrap()))
+            }
+            Granularity::Minutely => {
+                if !MINUTE_AT_RE.is_match(time) {
+                    return Err(format!("'{}' doesn't match the expected :SS form", time));
+                }
+                Ok((0, 0, time[1..].parse().unwrap()))
+            }
+            Granularity::None => {
+                Err("`.at()` can only be used with day, hour, minute or weekday jobs".to_string())
+            }
+        }
+    }
+
+    /// Recompute `next_run`, taking `at_time`/`weekday` into account when
+    /// they've been set so we land on the next matching wall-clock instant
+    /// instead of a fixed offset from `last_run`.
+    fn recompute_next_run(&mut self) {
+        self.next_run = Some(match self.at_time {
+            Some((hour, minute, second)) => {
+                let now = Tp::now();
+                // `parse_at` only fills in the fields the job's unit actually
+                // cares about (e.g. an hourly job's "hour" is always 0), so
+                // anchor the missing, more-significant fields to *now* rather
+                // than midnight - otherwise an hourly/minutely `.at()` would
+                // compute a `next_run` hours in the past and busy-fire forever.
+                let (anchor_hour, anchor_minute) = match self.granularity() {
+                    Granularity::Hourly => (now.hour(), minute),
+                    Granularity::Minutely => (now.hour(), now.minute()),
+                    Granularity::Daily | Granularity::None => (hour, minute),
+                };
+                let naive = now.date_naive()
+                    .and_hms_opt(anchor_hour, anchor_minute, second)
+                    .unwrap();
+                let mut candidate = Local.from_local_datetime(&naive).unwrap();
+                match self.weekday {
+                    Some(wd) => {
+                        while candidate.weekday() != wd || candidate <= now {
+                            candidate += Duration::days(1);
+                        }
+                    }
+                    None => {
+                        if candidate <= now {
+                            candidate += self.period_lower;
+                        }
+                    }
+                }
+                candidate
+            }
+            None => {
+                match self.period_upper {
+                    // An upper bound below the lower bound is invalid and
+                    // gets rejected by `validate()`; just fall back to the
+                    // lower bound here rather than sampling an empty range.
+                    Some(upper) if upper >= self.period_lower => {
+                        let lower_ms = self.period_lower.num_milliseconds();
+                        let upper_ms = upper.num_milliseconds();
+                        let span = rand::thread_rng().gen_range(lower_ms..=upper_ms);
+                        Tp::now() + Duration::milliseconds(span)
+                    }
+                    _ => self.last_run + self.period_lower,
+                }
+            }
+        });
     }
 
     /// Give the job a closure to run and validate that everything has been
     /// entered correctly.
-    pub fn do_(mut self, f: Func) -> Result<Job, String> {
-        self.func = Some(f);
+    pub fn do_(mut self, f: Func) -> Result<Self, String> {
+        self.func = Some(Arc::from(f));
         self.validate()
     }
 
     /// Check that a job is valid and ready to be run.
     fn validate(self) -> Result<Self, String> {
-        if self.duration.is_zero() {
+        let is_weekly = matches!(self.last_unit, Some(TimeSpan::Week) | Some(TimeSpan::Weeks));
+
+        if self.period_lower.is_zero() {
             Err("No duration entered".to_string())
         } else if self.func.is_none() {
             Err("No function supplied".to_string())
+        } else if is_weekly && self.at_time.is_some() && self.weekday.is_none() {
+            Err("A weekly job must have a weekday set before calling .at()".to_string())
+        } else if self.period_upper.map(|upper| upper < self.period_lower).unwrap_or(false) {
+            Err("Upper bound of a .to() range must be >= the lower bound".to_string())
         } else {
             Ok(self)
         }
@@ -146,19 +418,18 @@ impl Job {
     /// Run the job and update the metadata recording when the last time this
     /// job was run.
     pub fn execute(&mut self) -> Result<(), String> {
-        self.last_run = Local::now();
+        self.last_run = Tp::now();
 
         // Update the next run or set it to None if this was a
         // once off job
         if self.once_off {
             self.next_run = None;
         } else {
-            self.next_run = Some(Local::now() + self.duration);
+            self.recompute_next_run();
         }
 
-        match self.name {
-            Some(ref name) => info!("Running {}", name),
-            None => (),
+        if let Some(ref name) = self.name {
+            info!("Running {}", name);
         }
 
         match self.func {
@@ -172,10 +443,41 @@ impl Job {
         }
     }
 
+    /// A cheap, shareable handle to this job's closure. Used by the threaded
+    /// executor to run the job on a worker thread while the `Job` itself
+    /// stays behind in the scheduler's queue.
+    fn func_handle(&self) -> Option<Func> {
+        self.func.clone().map(|f| Box::new(move || f.call(())) as Func)
+    }
+
+    /// Hand off this job's closure for the caller to run elsewhere (e.g. on
+    /// a thread pool), immediately advancing `next_run` so the job isn't
+    /// dispatched a second time before the first run has even started.
+    pub(crate) fn dispatch(&mut self) -> Result<Func, String> {
+        let handle = match self.func_handle() {
+            Some(handle) => handle,
+            None => return Err("No function provided!".to_string()),
+        };
+
+        self.last_run = Tp::now();
+        if self.once_off {
+            self.next_run = None;
+        } else {
+            self.recompute_next_run();
+        }
+        self.times_run += 1;
+
+        if let Some(ref name) = self.name {
+            info!("Dispatching {}", name);
+        }
+
+        Ok(handle)
+    }
+
     /// Check whether the job needs to be run.
     pub fn ready(&self) -> bool {
         match self.next_run {
-            Some(next) => next <= Local::now(),
+            Some(next) => next <= Tp::now(),
             None => false,
         }
     }
@@ -190,16 +492,23 @@ impl Job {
 
 #[cfg(test)]
 mod tests {
-    use super::Job;
+    use super::Job as GenericJob;
     use std::sync::Mutex;
     use std::sync::Arc;
     use super::TimeSpan::*;
-    use chrono::{Duration, Local};
+    use chrono::{Datelike, Duration, Local, TimeZone, Timelike, Weekday};
+    use ChronoTimeProvider;
+    use MockTimeProvider;
+
+    // `Job` is generic over its `TimeProvider`, but every test below that
+    // doesn't name `MockTimeProvider` explicitly just wants the real
+    // clock - alias it so call sites don't need a turbofish on every line.
+    type Job = GenericJob<ChronoTimeProvider>;
 
     #[test]
     fn constructor() {
         let got = Job::new();
-        assert!(got.duration.is_zero());
+        assert!(got.period_lower.is_zero());
         assert!(got.name.is_none());
         assert!(!got.once_off);
     }
@@ -210,7 +519,7 @@ mod tests {
         assert!(job.is_periodic());
 
         let duration = Duration::minutes(5);
-        assert_eq!(job.duration, duration);
+        assert_eq!(job.period_lower, duration);
 
         assert!(job.func.is_some());
     }
@@ -268,7 +577,7 @@ mod tests {
     fn increment_with_and() {
         let job = Job::every(5, Minutes).and(18, Seconds);
         let duration = Duration::minutes(5) + Duration::seconds(18);
-        assert_eq!(job.duration, duration);
+        assert_eq!(job.period_lower, duration);
     }
 
     #[test]
@@ -293,4 +602,118 @@ mod tests {
         job.execute().unwrap();
         assert!(!job.ready());
     }
+
+    #[test]
+    fn at_rejects_a_malformed_time() {
+        let job = Job::every(1, Days).at("not-a-time");
+        assert!(job.is_err());
+    }
+
+    #[test]
+    fn at_accepts_hh_mm_ss_for_a_daily_job() {
+        let job = Job::every(1, Days).at("10:30:00").unwrap();
+        assert_eq!(job.at_time, Some((10, 30, 0)));
+    }
+
+    #[test]
+    fn at_accepts_hh_mm_for_a_daily_job() {
+        let job = Job::every(1, Days).at("09:05").unwrap();
+        assert_eq!(job.at_time, Some((9, 5, 0)));
+    }
+
+    #[test]
+    fn at_anchors_an_hourly_job_to_the_current_hour() {
+        MockTimeProvider::set(Local.ymd(2020, 1, 1).and_hms(13, 0, 0));
+        let job = GenericJob::<MockTimeProvider>::every(1, Hours)
+            .at("10:00")
+            .unwrap()
+            .do_(Box::new(|| ()))
+            .unwrap();
+
+        let next = job.next_run().unwrap();
+        assert_eq!((next.hour(), next.minute(), next.second()), (13, 10, 0));
+    }
+
+    #[test]
+    fn at_anchors_a_minutely_job_to_the_current_minute() {
+        MockTimeProvider::set(Local.ymd(2020, 1, 1).and_hms(13, 45, 0));
+        let job = GenericJob::<MockTimeProvider>::every(1, Minutes)
+            .at(":30")
+            .unwrap()
+            .do_(Box::new(|| ()))
+            .unwrap();
+
+        let next = job.next_run().unwrap();
+        assert_eq!((next.hour(), next.minute(), next.second()), (13, 45, 30));
+    }
+
+    #[test]
+    fn at_requires_a_weekday_for_weekly_jobs() {
+        let job = Job::every(1, Weeks).at("06:00:00").unwrap();
+        assert!(job.do_(Box::new(|| ())).is_err());
+    }
+
+    #[test]
+    fn every_weekday_lands_on_the_right_day() {
+        let job = Job::every_weekday(Weekday::Mon).at("06:00:00").unwrap();
+        let next = job.next_run().unwrap();
+        assert_eq!(next.weekday(), Weekday::Mon);
+    }
+
+    #[test]
+    fn to_sets_a_random_period_within_range() {
+        let job = Job::every(30, Minutes)
+            .to(45, Minutes)
+            .do_(Box::new(|| ()))
+            .unwrap();
+
+        let wait = job.next_run().unwrap() - Local::now();
+        assert!(wait >= Duration::minutes(30));
+        assert!(wait <= Duration::minutes(45));
+    }
+
+    #[test]
+    fn to_recomputes_next_run_immediately() {
+        // Regression test: `to()` used to only set `period_upper`, leaving
+        // `next_run` at the plain lower-bound value until something else
+        // happened to recompute it.
+        let job = Job::every(30, Minutes);
+        let plain_next_run = job.next_run;
+        let job = job.to(45, Minutes);
+        assert_ne!(job.next_run, plain_next_run);
+    }
+
+    #[test]
+    fn to_rejects_an_upper_bound_below_the_lower_bound() {
+        let job = Job::every(45, Minutes).to(30, Minutes).do_(Box::new(|| ()));
+        assert!(job.is_err());
+    }
+
+    #[test]
+    fn mock_clock_drives_readiness_without_sleeping() {
+        MockTimeProvider::set(Local::now());
+        let job = GenericJob::<MockTimeProvider>::every(5, Minutes)
+            .do_(Box::new(|| ()))
+            .unwrap();
+        assert!(!job.ready());
+
+        MockTimeProvider::advance(Duration::minutes(5));
+        assert!(job.ready());
+    }
+
+    #[test]
+    fn mock_clock_drives_weekday_alignment() {
+        // `every_weekday`/`.at()` only have an interesting, deterministic
+        // `next_run` to assert on once the clock itself is deterministic.
+        MockTimeProvider::set(Local.ymd(2020, 1, 1).and_hms(0, 0, 0)); // a Wednesday
+        let job = GenericJob::<MockTimeProvider>::every_weekday(Weekday::Mon)
+            .at("06:00:00")
+            .unwrap()
+            .do_(Box::new(|| ()))
+            .unwrap();
+
+        let next = job.next_run().unwrap();
+        assert_eq!(next.weekday(), Weekday::Mon);
+        assert_eq!((next.hour(), next.minute(), next.second()), (6, 0, 0));
+    }
 }
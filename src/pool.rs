@@ -0,0 +1,99 @@
+//! A small work-stealing thread pool used by `Scheduler::run_forever_threaded`
+//! so that one slow job doesn't block every other job from running on time.
+
+use std::iter;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use crossbeam::deque::{Injector, Stealer, Worker};
+use crossbeam::sync::{Parker, Unparker};
+
+use job::Func;
+
+/// A fixed set of worker threads that pull jobs from their own local queue
+/// first, falling back to stealing from the global injector (and from each
+/// other) when their local queue runs dry.
+pub struct ThreadPool {
+    injector: Arc<Injector<Func>>,
+    unparkers: Vec<Unparker>,
+    handles: Vec<thread::JoinHandle<()>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl ThreadPool {
+    /// Spin up `n_workers` worker threads.
+    pub fn new(n_workers: usize) -> ThreadPool {
+        let injector = Arc::new(Injector::new());
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let workers: Vec<Worker<Func>> = (0..n_workers).map(|_| Worker::new_fifo()).collect();
+        let stealers: Vec<Stealer<Func>> = workers.iter().map(Worker::stealer).collect();
+
+        let mut unparkers = Vec::with_capacity(n_workers);
+        let mut handles = Vec::with_capacity(n_workers);
+
+        for worker in workers {
+            let parker = Parker::new();
+            unparkers.push(parker.unparker().clone());
+
+            let injector = injector.clone();
+            let stealers = stealers.clone();
+            let shutdown = shutdown.clone();
+
+            handles.push(thread::spawn(move || {
+                loop {
+                    match find_task(&worker, &injector, &stealers) {
+                        Some(task) => task.call(()),
+                        None => {
+                            if shutdown.load(Ordering::SeqCst) {
+                                break;
+                            }
+                            parker.park();
+                        }
+                    }
+                }
+            }));
+        }
+
+        ThreadPool {
+            injector,
+            unparkers,
+            handles,
+            shutdown,
+        }
+    }
+
+    /// Push a job onto the global injector and wake up any parked workers.
+    pub fn spawn(&self, task: Func) {
+        self.injector.push(task);
+        for unparker in &self.unparkers {
+            unparker.unpark();
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        for unparker in &self.unparkers {
+            unparker.unpark();
+        }
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Find the next task to run: the worker's own queue first, then a batch
+/// stolen from the injector, then whatever a sibling worker is willing to
+/// give up.
+fn find_task(local: &Worker<Func>, global: &Injector<Func>, stealers: &[Stealer<Func>]) -> Option<Func> {
+    local.pop().or_else(|| {
+        iter::repeat_with(|| {
+            global.steal_batch_and_pop(local)
+                .or_else(|| stealers.iter().map(|s| s.steal()).collect())
+        }).find(|s| !s.is_retry())
+            .and_then(|s| s.success())
+    })
+}
@@ -1,33 +1,81 @@
 //! A scheduler who is in charge of checking whether a job is ready to be run
 //! and then executing it in the background on another thread.
 
+use std::collections::BinaryHeap;
 use std::fmt::{Formatter, Debug, Error};
-use chrono::{Duration, Local};
+use chrono::Duration;
 use std::thread;
 use super::Job;
+use job::JobId;
+use pool::ThreadPool;
+use time::{ChronoTimeProvider, TimeProvider};
 
 
-/// A job scheduler
-#[derive(Default)]
-pub struct Scheduler {
-    job_queue: Vec<Job>,
+/// A job scheduler.
+///
+/// Jobs are kept in a `BinaryHeap` ordered by `next_run`, so peeking the
+/// earliest deadline is O(1) and re-inserting a job after it runs is
+/// O(log n) - this matters once hundreds of jobs are registered.
+pub struct Scheduler<Tp: TimeProvider = ChronoTimeProvider> {
+    job_queue: BinaryHeap<Job<Tp>>,
 }
 
-impl Debug for Scheduler {
+impl<Tp: TimeProvider> Default for Scheduler<Tp> {
+    fn default() -> Self {
+        Scheduler { job_queue: BinaryHeap::new() }
+    }
+}
+
+impl<Tp: TimeProvider> Debug for Scheduler<Tp> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "Scheduler(job_queue={:?})", self.job_queue)
     }
 }
 
-impl Scheduler {
+impl<Tp: TimeProvider> Scheduler<Tp> {
     /// Create a new scheduler with an empty job queue.
-    pub fn new() -> Scheduler {
+    ///
+    /// Rust won't fall back to the `ChronoTimeProvider` default to resolve
+    /// inference on its own, so a call site with nothing else pinning `Tp`
+    /// needs an explicit type, e.g. `let s: Scheduler = Scheduler::new();`
+    /// or `Scheduler::<MockTimeProvider>::new()`.
+    pub fn new() -> Self {
         Scheduler::default()
     }
 
-    /// Add a Job to the job queue.
-    pub fn add_job(&mut self, job: Job) {
+    /// Add a Job to the job queue, returning a stable `JobId` that can be
+    /// used to cancel this specific job later, even if its tags collide
+    /// with other jobs.
+    pub fn add_job(&mut self, job: Job<Tp>) -> JobId {
+        let id = job.id();
         self.job_queue.push(job);
+        id
+    }
+
+    /// Get every job carrying the given tag.
+    pub fn get_jobs(&self, tag: &str) -> Vec<&Job<Tp>> {
+        self.job_queue.iter().filter(|j| j.has_tag(tag)).collect()
+    }
+
+    /// Drop every job carrying the given tag, returning how many were
+    /// removed.
+    pub fn clear(&mut self, tag: &str) -> usize {
+        let before = self.job_queue.len();
+        self.job_queue = self.job_queue.drain().filter(|j| !j.has_tag(tag)).collect();
+        before - self.job_queue.len()
+    }
+
+    /// Drop every job with the given name.
+    pub fn cancel_job(&mut self, name: &str) {
+        self.job_queue = self.job_queue.drain().filter(|j| !j.matches_name(name)).collect();
+    }
+
+    /// Drop the job with the given id, returning `true` if a job was
+    /// removed.
+    pub fn cancel_by_id(&mut self, id: JobId) -> bool {
+        let before = self.job_queue.len();
+        self.job_queue = self.job_queue.drain().filter(|j| j.id() != id).collect();
+        before != self.job_queue.len()
     }
 
     /// Check if there are any jobs that need to be run.
@@ -35,29 +83,36 @@ impl Scheduler {
         self.job_queue.iter().any(|j| j.ready())
     }
 
-    /// Get the time until the next job is due to be executed.
+    /// Get the time until the earliest job in the queue is due to be
+    /// executed, or `None` if no job has a `next_run` scheduled. Never
+    /// negative - if a job is already overdue this returns zero rather than
+    /// letting the caller sleep for a negative duration.
     pub fn time_to_next(&self) -> Option<Duration> {
-        let times: Vec<_> = self.job_queue.iter().filter(|j| j.next_run().is_some())
-            .map(|j| Local::now() - j.next_run().unwrap())
-            .collect();
-
-        times.iter().max().map(|t| t.clone())
+        self.job_queue.peek().and_then(Job::next_run).map(|next| {
+            let wait = next - Tp::now();
+            if wait > Duration::zero() { wait } else { Duration::zero() }
+        })
     }
 
-    /// Run any pending jobs and return the number of jobs run.
+    /// Run any pending jobs and return the number of jobs run. Because the
+    /// queue is ordered by `next_run`, we can stop as soon as the earliest
+    /// remaining job isn't ready yet.
     pub fn run_pending(&mut self) -> usize {
         let mut count = 0;
-        for job in &mut self.job_queue {
-            if job.ready() {
-                count += 1;
-                let result = job.execute();
+        let mut ran = Vec::new();
 
-                if result.is_err() {
-                    error!("{}", result.unwrap_err());
-                }
+        while self.job_queue.peek().map(|j| j.ready()).unwrap_or(false) {
+            let mut job = self.job_queue.pop().unwrap();
+            count += 1;
+
+            if let Err(e) = job.execute() {
+                error!("{}", e);
             }
+
+            ran.push(job);
         }
 
+        self.job_queue.extend(ran);
         count
     }
 
@@ -74,6 +129,47 @@ impl Scheduler {
             }
         }
     }
+
+    /// Dispatch any pending jobs onto `pool` instead of running them on the
+    /// caller's thread, and return the number of jobs dispatched.
+    pub fn run_pending_threaded(&mut self, pool: &ThreadPool) -> usize {
+        let mut count = 0;
+        let mut dispatched = Vec::new();
+
+        while self.job_queue.peek().map(|j| j.ready()).unwrap_or(false) {
+            let mut job = self.job_queue.pop().unwrap();
+
+            match job.dispatch() {
+                Ok(task) => {
+                    count += 1;
+                    pool.spawn(task);
+                }
+                Err(e) => error!("{}", e),
+            }
+
+            dispatched.push(job);
+        }
+
+        self.job_queue.extend(dispatched);
+        count
+    }
+
+    /// Like `run_forever`, but ready jobs are dispatched onto a `ThreadPool`
+    /// of `n_workers` worker threads, so one slow job no longer blocks every
+    /// other job from running on time.
+    pub fn run_forever_threaded(&mut self, n_workers: usize) {
+        let pool = ThreadPool::new(n_workers);
+        loop {
+            let delay = self.time_to_next();
+            match delay {
+                Some(wait_duration) => {
+                    thread::sleep(wait_duration.to_std().unwrap());
+                    self.run_pending_threaded(&pool);
+                },
+                None => break
+            }
+        }
+    }
 }
 
 
@@ -82,10 +178,18 @@ mod test {
     use std::thread::sleep;
     use std::time::Duration as Duration_std;
     use std::sync::{Mutex, Arc};
-    use super::Scheduler;
-    use super::super::Job;
+    use chrono::Duration;
+    use super::Scheduler as GenericScheduler;
+    use super::super::Job as GenericJob;
+    use super::super::ChronoTimeProvider;
     use super::super::TimeSpan::*;
 
+    // Both `Job` and `Scheduler` are generic over their `TimeProvider`;
+    // every test here just wants the real clock, so alias it to avoid a
+    // turbofish on every call site.
+    type Job = GenericJob<ChronoTimeProvider>;
+    type Scheduler = GenericScheduler<ChronoTimeProvider>;
+
     #[test]
     fn constructor() {
         let sched = Scheduler::new();
@@ -94,8 +198,8 @@ mod test {
 
     #[test]
     fn add_job_to_queue() {
-        let job = Job::every(5, Minutes).run(Box::new(|| ())).unwrap();
-        let job_2 = Job::every(5, Minutes).run(Box::new(|| ())).unwrap();
+        let job = Job::every(5, Minutes).do_(Box::new(|| ())).unwrap();
+        let job_2 = Job::every(5, Minutes).do_(Box::new(|| ())).unwrap();
         let mut sched = Scheduler::new();
         assert!(sched.job_queue.is_empty());
 
@@ -116,7 +220,7 @@ mod test {
 
     #[test]
     fn queue_with_pending_task() {
-        let job = Job::every(10, Milliseconds).run(Box::new(|| ())).unwrap();
+        let job = Job::every(10, Milliseconds).do_(Box::new(|| ())).unwrap();
 
         // Wait until after the job is ready
         sleep(Duration_std::from_millis(11));
@@ -137,7 +241,7 @@ mod test {
         let num_2 = num.clone();
 
         let job = Job::every(10, Milliseconds)
-            .run(Box::new(move || {
+            .do_(Box::new(move || {
                 let mut n = num_2.lock().unwrap();
                 *n = 42;
             }))
@@ -162,4 +266,101 @@ mod test {
         // Make sure the job actually changed our number
         assert_eq!(*num.lock().unwrap(), 42);
     }
+
+    #[test]
+    fn run_pending_threaded_dispatches_ready_jobs() {
+        use super::super::pool::ThreadPool;
+
+        let num: Arc<Mutex<u32>> = Arc::new(Mutex::new(0));
+        let num_2 = num.clone();
+
+        let job = Job::every(10, Milliseconds)
+            .do_(Box::new(move || {
+                let mut n = num_2.lock().unwrap();
+                *n = 42;
+            }))
+            .unwrap();
+
+        sleep(Duration_std::from_millis(11));
+        assert!(job.ready());
+
+        let mut sched = Scheduler::new();
+        sched.add_job(job);
+
+        let pool = ThreadPool::new(2);
+        let num_dispatched = sched.run_pending_threaded(&pool);
+        assert_eq!(num_dispatched, 1);
+
+        // The job runs on a worker thread, so give it a moment to finish.
+        sleep(Duration_std::from_millis(50));
+        assert_eq!(*num.lock().unwrap(), 42);
+    }
+
+    #[test]
+    fn get_jobs_finds_everything_with_a_tag() {
+        let mut sched = Scheduler::new();
+        sched.add_job(Job::every(5, Minutes).tag("daily-report").do_(Box::new(|| ())).unwrap());
+        sched.add_job(Job::every(5, Minutes).tag("daily-report").do_(Box::new(|| ())).unwrap());
+        sched.add_job(Job::every(5, Minutes).tag("cleanup").do_(Box::new(|| ())).unwrap());
+
+        assert_eq!(sched.get_jobs("daily-report").len(), 2);
+        assert_eq!(sched.get_jobs("cleanup").len(), 1);
+        assert_eq!(sched.get_jobs("nonexistent").len(), 0);
+    }
+
+    #[test]
+    fn clear_removes_every_job_with_a_tag() {
+        let mut sched = Scheduler::new();
+        sched.add_job(Job::every(5, Minutes).tag("daily-report").do_(Box::new(|| ())).unwrap());
+        sched.add_job(Job::every(5, Minutes).tag("daily-report").do_(Box::new(|| ())).unwrap());
+        sched.add_job(Job::every(5, Minutes).tag("cleanup").do_(Box::new(|| ())).unwrap());
+
+        assert_eq!(sched.clear("daily-report"), 2);
+        assert_eq!(sched.job_queue.len(), 1);
+    }
+
+    #[test]
+    fn cancel_job_removes_by_name() {
+        let mut sched = Scheduler::new();
+        sched.add_job(Job::every(5, Minutes).name("backup").do_(Box::new(|| ())).unwrap());
+        sched.add_job(Job::every(5, Minutes).name("cleanup").do_(Box::new(|| ())).unwrap());
+
+        sched.cancel_job("backup");
+        assert_eq!(sched.job_queue.len(), 1);
+    }
+
+    #[test]
+    fn cancel_by_id_removes_a_specific_job_even_with_shared_tags() {
+        let mut sched = Scheduler::new();
+        let first = sched.add_job(Job::every(5, Minutes).tag("shared").do_(Box::new(|| ())).unwrap());
+        sched.add_job(Job::every(5, Minutes).tag("shared").do_(Box::new(|| ())).unwrap());
+
+        assert!(sched.cancel_by_id(first));
+        assert_eq!(sched.job_queue.len(), 1);
+        assert!(!sched.cancel_by_id(first));
+    }
+
+    #[test]
+    fn time_to_next_is_never_negative() {
+        let job = Job::every(10, Milliseconds).do_(Box::new(|| ())).unwrap();
+        sleep(Duration_std::from_millis(11));
+        assert!(job.ready());
+
+        let mut sched = Scheduler::new();
+        sched.add_job(job);
+
+        assert_eq!(sched.time_to_next(), Some(Duration::zero()));
+    }
+
+    #[test]
+    fn time_to_next_picks_the_earliest_job() {
+        let mut sched = Scheduler::new();
+        sched.add_job(Job::every(1, Hours).do_(Box::new(|| ())).unwrap());
+        sched.add_job(Job::every(5, Minutes).do_(Box::new(|| ())).unwrap());
+        sched.add_job(Job::every(1, Days).do_(Box::new(|| ())).unwrap());
+
+        let wait = sched.time_to_next().unwrap();
+        assert!(wait <= Duration::minutes(5));
+        assert!(wait > Duration::minutes(4));
+    }
 }
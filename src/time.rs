@@ -0,0 +1,70 @@
+//! A pluggable source of "now", so that `Job` and `Scheduler` can be driven
+//! by a deterministic clock in tests instead of sleeping and hoping.
+
+use std::cell::RefCell;
+use chrono::{DateTime, Duration, Local};
+
+/// A source of the current time. `Job` and `Scheduler` are generic over this
+/// so tests can swap in a `MockTimeProvider` instead of waiting on the real
+/// clock.
+pub trait TimeProvider {
+    /// The current time, according to this provider.
+    fn now() -> DateTime<Local>;
+}
+
+/// The default `TimeProvider`, backed by the real wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChronoTimeProvider;
+
+impl TimeProvider for ChronoTimeProvider {
+    fn now() -> DateTime<Local> {
+        Local::now()
+    }
+}
+
+thread_local! {
+    // Per-thread rather than a single process-wide clock, so that
+    // `MockTimeProvider` tests running concurrently (the cargo-test
+    // default) don't race and clobber each other's clock.
+    static MOCK_NOW: RefCell<DateTime<Local>> = RefCell::new(Local::now());
+}
+
+/// A `TimeProvider` whose clock is controlled by hand, for deterministic
+/// tests.
+///
+///     extern crate chrono;
+///     use sched::{Job, MockTimeProvider, TimeProvider};
+///     use sched::TimeSpan::Minutes;
+///     use chrono::Duration;
+///
+///     let job: Job<MockTimeProvider> = Job::every(5, Minutes)
+///         .do_(Box::new(|| ()))
+///         .unwrap();
+///     assert!(!job.ready());
+///
+///     MockTimeProvider::advance(Duration::minutes(5));
+///     assert!(job.ready());
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MockTimeProvider;
+
+impl MockTimeProvider {
+    /// Set the mock clock to an absolute point in time.
+    pub fn set(now: DateTime<Local>) {
+        MOCK_NOW.with(|cell| *cell.borrow_mut() = now);
+    }
+
+    /// Move the mock clock forward (or backward, with a negative duration)
+    /// by the given amount.
+    pub fn advance(duration: Duration) {
+        MOCK_NOW.with(|cell| {
+            let mut now = cell.borrow_mut();
+            *now += duration;
+        });
+    }
+}
+
+impl TimeProvider for MockTimeProvider {
+    fn now() -> DateTime<Local> {
+        MOCK_NOW.with(|cell| *cell.borrow())
+    }
+}